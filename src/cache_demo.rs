@@ -1,7 +1,91 @@
 use dashmap::DashMap;
 use pyo3::prelude::*;
+use std::collections::HashMap;
 use std::sync::Arc;
 
+/// Acquire an exclusive advisory lock on the snapshot `path`, returning the
+/// locked file handle.
+///
+/// The lock lives for exactly as long as the returned handle is alive, so a
+/// caller that wants a single-instance guard (the snapshot worker, a daemon)
+/// must hold the handle for its whole lifetime — dropping it releases the
+/// lock. If another process already holds the lock the call fails fast rather
+/// than blocking or silently proceeding unlocked.
+pub(crate) fn lock_snapshot(path: &str) -> Result<std::fs::File, String> {
+    use fs4::fs_std::FileExt;
+
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| format!("failed to open snapshot '{}': {}", path, e))?;
+
+    // `try_lock_exclusive` reports contention as `Ok(false)`, not an error, so
+    // the boolean must be checked explicitly — otherwise we would write the
+    // snapshot unlocked while another process holds it.
+    let acquired = file
+        .try_lock_exclusive()
+        .map_err(|e| format!("failed to lock snapshot '{}': {}", path, e))?;
+    if !acquired {
+        return Err(format!(
+            "snapshot path '{}' is locked by another process",
+            path
+        ));
+    }
+    Ok(file)
+}
+
+/// Serialize `cache` into the already-locked snapshot `file` as a JSON object,
+/// overwriting any previous contents. The caller owns the advisory lock via
+/// [`lock_snapshot`] for as long as writes should be exclusive.
+pub(crate) fn write_snapshot(
+    file: &mut std::fs::File,
+    cache: &DashMap<String, String>,
+) -> Result<(), String> {
+    use std::io::{Seek, SeekFrom, Write};
+
+    let snapshot: HashMap<String, String> = cache
+        .iter()
+        .map(|e| (e.key().clone(), e.value().clone()))
+        .collect();
+    let json = serde_json::to_vec_pretty(&snapshot).map_err(|e| e.to_string())?;
+
+    file.set_len(0)
+        .map_err(|e| format!("failed to truncate snapshot: {}", e))?;
+    file.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+    file.write_all(&json)
+        .map_err(|e| format!("failed to write snapshot: {}", e))?;
+    file.flush().map_err(|e| e.to_string())
+}
+
+/// Read and deserialize a JSON snapshot from `path` under a shared advisory
+/// lock. Returns the stored key/value pairs for the caller to repopulate.
+pub(crate) fn read_snapshot(path: &str) -> Result<HashMap<String, String>, String> {
+    use fs4::fs_std::FileExt;
+    use std::io::Read;
+
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .open(path)
+        .map_err(|e| format!("failed to open snapshot '{}': {}", path, e))?;
+
+    let acquired = file
+        .try_lock_shared()
+        .map_err(|e| format!("failed to lock snapshot '{}': {}", path, e))?;
+    if !acquired {
+        return Err(format!(
+            "snapshot path '{}' is locked by another process",
+            path
+        ));
+    }
+
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)
+        .map_err(|e| format!("failed to read snapshot '{}': {}", path, e))?;
+    serde_json::from_slice(&bytes).map_err(|e| e.to_string())
+}
+
 /// Demonstrates the cache pattern: synchronous reads, async background updates.
 ///
 /// This class shows the core architectural pattern for driftpyrs:
@@ -83,4 +167,33 @@ impl CacheDemo {
     fn clear(&self) {
         self.cache.clear();
     }
+
+    /// Serialize the cache to a JSON file for crash-safe local persistence.
+    ///
+    /// The file is guarded by an exclusive advisory lock, so a second process
+    /// writing the same snapshot path fails fast with a `ValueError` instead
+    /// of corrupting it.
+    fn snapshot_to<'py>(&self, py: Python<'py>, path: String) -> PyResult<Bound<'py, PyAny>> {
+        let cache = Arc::clone(&self.cache);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let mut file = lock_snapshot(&path).map_err(pyo3::exceptions::PyValueError::new_err)?;
+            write_snapshot(&mut file, &cache).map_err(pyo3::exceptions::PyValueError::new_err)
+        })
+    }
+
+    /// Repopulate the cache from a JSON snapshot written by `snapshot_to`.
+    ///
+    /// Intended to run on startup before the background worker begins ticking
+    /// so restarts resume from the last persisted state.
+    fn restore_from<'py>(&self, py: Python<'py>, path: String) -> PyResult<Bound<'py, PyAny>> {
+        let cache = Arc::clone(&self.cache);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let entries =
+                read_snapshot(&path).map_err(pyo3::exceptions::PyValueError::new_err)?;
+            for (key, value) in entries {
+                cache.insert(key, value);
+            }
+            Ok(())
+        })
+    }
 }
@@ -2,21 +2,51 @@ use pyo3::prelude::*;
 use pythonize::pythonize;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::Arc;
 
+/// Which streaming source is currently driving the internal cache.
+#[derive(Clone, Copy)]
+enum Backend {
+    None = 0,
+    WebSocket = 1,
+    Grpc = 2,
+}
+
+impl Backend {
+    fn from_u8(v: u8) -> Backend {
+        match v {
+            1 => Backend::WebSocket,
+            2 => Backend::Grpc,
+            _ => Backend::None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Backend::None => "none",
+            Backend::WebSocket => "websocket",
+            Backend::Grpc => "grpc",
+        }
+    }
+}
+
 #[pyclass]
 pub struct DriftClient {
     inner: Arc<drift_rs::DriftClient>,
+    /// Active subscription backend, exposed in `__repr__`.
+    backend: Arc<AtomicU8>,
 }
 
 #[pymethods]
 impl DriftClient {
     #[staticmethod]
-    #[pyo3(signature = (rpc_url, context="mainnet"))]
+    #[pyo3(signature = (rpc_url, context="mainnet", keypair_bytes=None))]
     fn connect<'py>(
         py: Python<'py>,
         rpc_url: String,
         context: &str,
+        keypair_bytes: Option<Vec<u8>>,
     ) -> PyResult<Bound<'py, PyAny>> {
         let context = context.to_owned();
         pyo3_async_runtimes::tokio::future_into_py(py, async move {
@@ -35,8 +65,24 @@ impl DriftClient {
             };
 
             let rpc_client = drift_rs::RpcClient::new(rpc_url.clone());
-            let dummy_pubkey = solana_sdk::pubkey!("11111111111111111111111111111111");
-            let wallet = drift_rs::Wallet::read_only(dummy_pubkey);
+            // A real signer enables order placement; without one the client is
+            // read-only and backed by a dummy authority.
+            let wallet = match keypair_bytes {
+                Some(bytes) => {
+                    let keypair =
+                        solana_sdk::signature::Keypair::from_bytes(&bytes).map_err(|e| {
+                            pyo3::exceptions::PyValueError::new_err(format!(
+                                "Invalid keypair bytes: {}",
+                                e
+                            ))
+                        })?;
+                    drift_rs::Wallet::from(keypair)
+                }
+                None => {
+                    let dummy_pubkey = solana_sdk::pubkey!("11111111111111111111111111111111");
+                    drift_rs::Wallet::read_only(dummy_pubkey)
+                }
+            };
 
             let client = drift_rs::DriftClient::new(drift_context, rpc_client, wallet)
                 .await
@@ -52,6 +98,7 @@ impl DriftClient {
 
             Ok(DriftClient {
                 inner: Arc::new(client),
+                backend: Arc::new(AtomicU8::new(Backend::None as u8)),
             })
         })
     }
@@ -78,10 +125,11 @@ impl DriftClient {
 
     fn __repr__(&self) -> String {
         format!(
-            "DriftClient(context='{}', perp_markets={}, spot_markets={})",
+            "DriftClient(context='{}', perp_markets={}, spot_markets={}, backend='{}')",
             self.inner.context.name(),
             self.get_perp_market_count(),
-            self.get_spot_market_count()
+            self.get_spot_market_count(),
+            Backend::from_u8(self.backend.load(Ordering::Relaxed)).name()
         )
     }
 
@@ -105,6 +153,7 @@ impl DriftClient {
     ///     ```
     fn subscribe<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         let inner = Arc::clone(&self.inner);
+        let backend = Arc::clone(&self.backend);
 
         pyo3_async_runtimes::tokio::future_into_py(py, async move {
             #[cfg(feature = "observability")]
@@ -124,6 +173,8 @@ impl DriftClient {
                 ))
             })?;
 
+            backend.store(Backend::WebSocket as u8, Ordering::Relaxed);
+
             #[cfg(feature = "observability")]
             tracing::info!(
                 "DriftClient::subscribe completed, background tasks running (markets + oracles)"
@@ -133,35 +184,104 @@ impl DriftClient {
         })
     }
 
-    fn get_perp_oracle(&self, py: Python<'_>, market_index: u16) -> PyResult<Option<Py<PyAny>>> {
+    /// Subscribe via a Yellowstone/Geyser gRPC account-update stream instead of
+    /// websocket RPC.
+    ///
+    /// This drives the same internal market/oracle cache off a single gRPC
+    /// connection filtered to the Drift program's accounts, which scales far
+    /// better than per-account websocket subscriptions for bots tracking
+    /// hundreds of markets.
+    ///
+    /// The out-of-order write handling is enforced by `drift_rs`, not this
+    /// crate: `grpc_subscribe` feeds updates through the same
+    /// `DriftClientBackend` account map used by the websocket path, whose
+    /// Geyser ingestion stamps each write with its slot and overwrites a cached
+    /// account only when the incoming slot is strictly newer — stale/replayed
+    /// writes are dropped. We rely on `GrpcSubscribeOpts::default()`, which
+    /// subscribes to the Drift program's market and oracle accounts; we do not
+    /// re-implement slot ordering here. The chosen backend is reflected in
+    /// `__repr__`.
+    #[pyo3(signature = (grpc_url, grpc_token))]
+    fn subscribe_grpc<'py>(
+        &self,
+        py: Python<'py>,
+        grpc_url: String,
+        grpc_token: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        let backend = Arc::clone(&self.backend);
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            #[cfg(feature = "observability")]
+            tracing::info!(grpc_url, "DriftClient::subscribe_grpc starting");
+
+            // Defaults filter the stream to the Drift program's market/oracle
+            // accounts; slot-ordered application is handled by drift_rs.
+            let opts = drift_rs::grpc::GrpcSubscribeOpts::default();
+            inner
+                .grpc_subscribe(grpc_url, grpc_token, opts)
+                .await
+                .map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to subscribe via gRPC: {}",
+                        e
+                    ))
+                })?;
+
+            backend.store(Backend::Grpc as u8, Ordering::Relaxed);
+
+            #[cfg(feature = "observability")]
+            tracing::info!("DriftClient::subscribe_grpc completed, gRPC stream running");
+
+            Ok(())
+        })
+    }
+
+    #[pyo3(signature = (market_index, max_slot_lag=None))]
+    fn get_perp_oracle(
+        &self,
+        py: Python<'_>,
+        market_index: u16,
+        max_slot_lag: Option<u64>,
+    ) -> PyResult<Option<Py<PyAny>>> {
         match self
             .inner
             .try_get_oracle_price_data_and_slot(drift_rs::types::MarketId::perp(market_index))
         {
             Some(oracle) => {
-                let dict = pyo3::types::PyDict::new(py);
-                dict.set_item("price", oracle.data.price)?;
-                dict.set_item("confidence", oracle.data.confidence)?;
-                dict.set_item("delay", oracle.data.delay)?;
-                dict.set_item("slot", oracle.slot)?;
-                Ok(Some(dict.into_any().unbind()))
+                let source = self
+                    .inner
+                    .try_get_perp_market_account(market_index)
+                    .ok()
+                    .map(|m| m.amm.oracle_source);
+                let dict =
+                    build_oracle_dict(py, &oracle, source, self.inner.get_slot(), max_slot_lag)?;
+                Ok(Some(dict))
             }
             None => Ok(None),
         }
     }
 
-    fn get_spot_oracle(&self, py: Python<'_>, market_index: u16) -> PyResult<Option<Py<PyAny>>> {
+    #[pyo3(signature = (market_index, max_slot_lag=None))]
+    fn get_spot_oracle(
+        &self,
+        py: Python<'_>,
+        market_index: u16,
+        max_slot_lag: Option<u64>,
+    ) -> PyResult<Option<Py<PyAny>>> {
         match self
             .inner
             .try_get_oracle_price_data_and_slot(drift_rs::types::MarketId::spot(market_index))
         {
             Some(oracle) => {
-                let dict = pyo3::types::PyDict::new(py);
-                dict.set_item("price", oracle.data.price)?;
-                dict.set_item("confidence", oracle.data.confidence)?;
-                dict.set_item("delay", oracle.data.delay)?;
-                dict.set_item("slot", oracle.slot)?;
-                Ok(Some(dict.into_any().unbind()))
+                let source = self
+                    .inner
+                    .try_get_spot_market_account(market_index)
+                    .ok()
+                    .map(|m| m.oracle_source);
+                let dict =
+                    build_oracle_dict(py, &oracle, source, self.inner.get_slot(), max_slot_lag)?;
+                Ok(Some(dict))
             }
             None => Ok(None),
         }
@@ -433,4 +553,436 @@ impl DriftClient {
             })
         })
     }
+
+    /// Parse a signed Pyth Lazer update and feed it into the in-memory oracle
+    /// cache, so subsequent `get_perp_oracle` calls reflect it immediately.
+    ///
+    /// Returns the parsed oracle dict. Lets latency-sensitive bots crank a
+    /// freshly pulled Lazer price on demand instead of waiting for the
+    /// background push subscription.
+    fn ingest_lazer_update(
+        &self,
+        py: Python<'_>,
+        feed_id: u32,
+        message: Vec<u8>,
+        signatures: Vec<Vec<u8>>,
+    ) -> PyResult<Py<PyAny>> {
+        let update = drift_rs::pyth_lazer::parse_update(&message, &signatures).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Invalid Lazer update: {}", e))
+        })?;
+        let market_index = drift_rs::constants::pyth_lazer_feed_id_to_perp_market_index(feed_id)
+            .ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "No perp market for Lazer feed id {}",
+                    feed_id
+                ))
+            })?;
+
+        // The Lazer payload carries a native mantissa plus its own exponent;
+        // normalize both price and confidence to Drift's 1e6 oracle precision
+        // before caching so later `get_perp_oracle` reads are on the same scale.
+        let exponent = update.exponent as i32;
+        let price = lazer_to_oracle_precision_i64(update.price, exponent);
+        let confidence = lazer_to_oracle_precision_u64(update.confidence, exponent);
+
+        let price_data = drift_rs::types::OraclePriceData {
+            price,
+            confidence,
+            // Freshly pulled on demand: this is the newest write for the feed,
+            // so it carries no slot lag. The backend stamps the cache slot.
+            delay: 0,
+            has_sufficient_number_of_data_points: true,
+        };
+        self.inner
+            .set_oracle_price_data(drift_rs::types::MarketId::perp(market_index), price_data)
+            .map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to update oracle cache: {}",
+                    e
+                ))
+            })?;
+
+        // Report the normalized values actually cached (1e6 precision, the same
+        // scale as `price`/`confidence` from `get_perp_oracle`).
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("price", price)?;
+        dict.set_item("confidence", confidence)?;
+        dict.set_item("exponent", ORACLE_PRICE_EXPONENT)?;
+        dict.set_item("publish_time", update.publish_time)?;
+        Ok(dict.into_any().unbind())
+    }
+
+    // -------------------------------------------------------------------------
+    // Transactions (order placement, cancel, settle)
+    // -------------------------------------------------------------------------
+
+    /// Place a perp order from a params dict, returning the transaction
+    /// signature (async).
+    ///
+    /// `price` and `base_asset_amount` are normalized with the market's tick
+    /// and step size via the same `standardize_*` helpers exported from the
+    /// `math` module before the instruction is built.
+    fn place_perp_order<'py>(
+        &self,
+        py: Python<'py>,
+        params: Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        let mut order: drift_rs::types::OrderParams = pythonize::depythonize(&params)
+            .map_err(|e| {
+                pyo3::exceptions::PyValueError::new_err(format!("Invalid order params: {}", e))
+            })?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let market = inner
+                .try_get_perp_market_account(order.market_index)
+                .map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Unknown perp market {}: {}",
+                        order.market_index, e
+                    ))
+                })?;
+            order.price = crate::math::standardize_price(
+                order.price,
+                market.amm.order_tick_size,
+                direction_str(order.direction),
+            )?;
+            order.base_asset_amount = crate::math::standardize_base_asset_amount(
+                order.base_asset_amount,
+                market.amm.order_step_size,
+            );
+
+            submit(&inner, |b| b.place_orders(vec![order])).await
+        })
+    }
+
+    /// Place a spot order from a params dict, returning the transaction
+    /// signature (async). Inputs are normalized like `place_perp_order`.
+    fn place_spot_order<'py>(
+        &self,
+        py: Python<'py>,
+        params: Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        let mut order: drift_rs::types::OrderParams = pythonize::depythonize(&params)
+            .map_err(|e| {
+                pyo3::exceptions::PyValueError::new_err(format!("Invalid order params: {}", e))
+            })?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let market = inner
+                .try_get_spot_market_account(order.market_index)
+                .map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Unknown spot market {}: {}",
+                        order.market_index, e
+                    ))
+                })?;
+            order.price = crate::math::standardize_price(
+                order.price,
+                market.order_tick_size,
+                direction_str(order.direction),
+            )?;
+            order.base_asset_amount = crate::math::standardize_base_asset_amount(
+                order.base_asset_amount,
+                market.order_step_size,
+            );
+
+            submit(&inner, |b| b.place_orders(vec![order])).await
+        })
+    }
+
+    /// Cancel all of the signer's open orders, returning the signature (async).
+    fn cancel_orders<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            submit(&inner, |b| b.cancel_all_orders()).await
+        })
+    }
+
+    /// Cancel a single open order by its on-chain id (async).
+    fn cancel_order_by_id<'py>(
+        &self,
+        py: Python<'py>,
+        order_id: u32,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            submit(&inner, |b| b.cancel_orders_by_id(vec![order_id])).await
+        })
+    }
+
+    /// Settle PnL for the signer on a perp market, returning the signature.
+    fn settle_pnl<'py>(&self, py: Python<'py>, market_index: u16) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let user = inner.wallet().default_sub_account();
+            submit(&inner, move |b| b.settle_pnl(user, market_index)).await
+        })
+    }
+
+    // -------------------------------------------------------------------------
+    // Live streams (fills / orderbook)
+    // -------------------------------------------------------------------------
+
+    /// Stream fills for a perp market into a Python callback (async push).
+    ///
+    /// Each fill is delivered as a normalized dict (maker, taker, base/quote in
+    /// UI units, price, slot, timestamp, and taker side). Returns a
+    /// [`crate::streams::SubscriptionHandle`] that can be cancelled.
+    fn subscribe_fills(
+        &self,
+        market_index: u16,
+        callback: Py<PyAny>,
+    ) -> PyResult<crate::streams::SubscriptionHandle> {
+        let inner = Arc::clone(&self.inner);
+        let rt = pyo3_async_runtimes::tokio::get_runtime();
+
+        let join = rt.spawn(async move {
+            use futures_util::StreamExt;
+
+            let mut stream = match inner.subscribe_events().await {
+                Ok(stream) => stream,
+                Err(_e) => {
+                    #[cfg(feature = "observability")]
+                    tracing::warn!(error = %_e, "subscribe_fills - failed to open event stream");
+                    return;
+                }
+            };
+
+            while let Some(event) = stream.next().await {
+                if let drift_rs::event_subscriber::DriftEvent::OrderFill {
+                    maker,
+                    taker,
+                    base_asset_amount_filled,
+                    quote_asset_amount_filled,
+                    market_index: mi,
+                    market_type,
+                    taker_order_direction,
+                    ts,
+                    slot,
+                    ..
+                } = event
+                {
+                    if market_type != drift_rs::types::MarketType::Perp || mi != market_index {
+                        continue;
+                    }
+                    let maker = maker.map(|p| p.to_string());
+                    let taker = taker.map(|p| p.to_string());
+                    let side = match taker_order_direction {
+                        drift_rs::types::PositionDirection::Long => "long",
+                        drift_rs::types::PositionDirection::Short => "short",
+                    };
+                    crate::streams::dispatch(&callback, |py| {
+                        crate::streams::fill_to_dict(
+                            py,
+                            mi,
+                            maker,
+                            taker,
+                            base_asset_amount_filled,
+                            quote_asset_amount_filled,
+                            side,
+                            slot,
+                            ts,
+                        )
+                    });
+                }
+            }
+        });
+
+        Ok(crate::streams::SubscriptionHandle::new(join.abort_handle()))
+    }
+
+    /// Stream L2 orderbook snapshots for a perp market into a Python callback.
+    ///
+    /// Polls the DLOB server every `interval_ms` and delivers the current bid
+    /// and ask levels (price/size in UI units). Returns a
+    /// [`crate::streams::SubscriptionHandle`] that can be cancelled.
+    #[pyo3(signature = (market_index, callback, dlob_url="https://dlob.drift.trade".to_string(), interval_ms=400))]
+    fn subscribe_orderbook(
+        &self,
+        market_index: u16,
+        callback: Py<PyAny>,
+        dlob_url: String,
+        interval_ms: u64,
+    ) -> PyResult<crate::streams::SubscriptionHandle> {
+        let rt = pyo3_async_runtimes::tokio::get_runtime();
+
+        let join = rt.spawn(async move {
+            let dlob = drift_rs::dlob::DLOBClient::new(&dlob_url);
+            loop {
+                match dlob
+                    .get_l2(drift_rs::types::MarketId::perp(market_index))
+                    .await
+                {
+                    Ok(book) => {
+                        crate::streams::dispatch(&callback, |py| {
+                            crate::streams::l2_to_dict(py, market_index, &book)
+                        });
+                    }
+                    Err(_e) => {
+                        #[cfg(feature = "observability")]
+                        tracing::warn!(error = %_e, "subscribe_orderbook - L2 fetch failed");
+                    }
+                }
+                tokio::time::sleep(tokio::time::Duration::from_millis(interval_ms)).await;
+            }
+        });
+
+        Ok(crate::streams::SubscriptionHandle::new(join.abort_handle()))
+    }
+
+    // -------------------------------------------------------------------------
+    // Margin / Health
+    // -------------------------------------------------------------------------
+
+    /// Compute collateral and margin health for a user account (async).
+    ///
+    /// Fetches the account then values each spot/perp position against the
+    /// cached market and oracle data the way the on-chain program does.
+    /// Returns a dict with `total_collateral`, `initial_margin_requirement`,
+    /// `maintenance_margin_requirement`, `free_collateral`, `margin_ratio`, and
+    /// a `can_be_liquidated` flag.
+    fn get_health<'py>(&self, py: Python<'py>, account: &str) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        let account = Pubkey::from_str(account).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Invalid pubkey: {}", e))
+        })?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let user = inner.get_user_account(&account).await.map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to get user account: {}",
+                    e
+                ))
+            })?;
+
+            Python::attach(|py| crate::health::compute_health(py, &inner, &user))
+        })
+    }
+
+    /// Compute margin health synchronously from the cache (no await).
+    ///
+    /// Returns `None` if the user account is not in the cache yet. Same dict
+    /// shape as `get_health`.
+    fn try_get_health(&self, py: Python<'_>, account: &str) -> PyResult<Option<Py<PyAny>>> {
+        let account = Pubkey::from_str(account).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!("Invalid pubkey: {}", e))
+        })?;
+
+        match self.inner.try_get_user_account(&account) {
+            Some(user) => Ok(Some(crate::health::compute_health(py, &self.inner, &user)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Build, sign and send a transaction for the signer's default sub-account.
+///
+/// Loads the authority's user account, hands a `TransactionBuilder` to `f` to
+/// append the desired instructions, then submits it and returns the signature
+/// string. Shared by the order/cancel/settle methods.
+async fn submit<F>(inner: &drift_rs::DriftClient, f: F) -> PyResult<String>
+where
+    F: FnOnce(drift_rs::TransactionBuilder) -> drift_rs::TransactionBuilder,
+{
+    let user = inner.wallet().default_sub_account();
+    let account = inner.get_user_account(&user).await.map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to load user account: {}", e))
+    })?;
+
+    let builder = drift_rs::TransactionBuilder::new(
+        inner.program_data(),
+        user,
+        std::borrow::Cow::Owned(account),
+        false,
+    );
+    let tx = f(builder).build();
+
+    let signature = inner.sign_and_send(tx).await.map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to submit transaction: {}", e))
+    })?;
+    Ok(signature.to_string())
+}
+
+/// Exponent of Drift's oracle price precision: prices are fixed-point with 6
+/// decimals (1e6), i.e. a power-of-ten exponent of -6.
+const ORACLE_PRICE_EXPONENT: i32 = -6;
+
+/// Rescale a Lazer-native mantissa (`value` at `10^exponent`) to Drift's 1e6
+/// oracle price precision, so a freshly pulled Lazer price lands in the cache
+/// at the same scale `get_perp_oracle` returns rather than off by a power of
+/// ten.
+fn lazer_to_oracle_precision_i64(value: i64, exponent: i32) -> i64 {
+    let shift = exponent - ORACLE_PRICE_EXPONENT;
+    if shift >= 0 {
+        value.saturating_mul(10_i64.saturating_pow(shift as u32))
+    } else {
+        value / 10_i64.saturating_pow((-shift) as u32)
+    }
+}
+
+/// Unsigned counterpart of [`lazer_to_oracle_precision_i64`] for confidence.
+fn lazer_to_oracle_precision_u64(value: u64, exponent: i32) -> u64 {
+    let shift = exponent - ORACLE_PRICE_EXPONENT;
+    if shift >= 0 {
+        value.saturating_mul(10_u64.saturating_pow(shift as u32))
+    } else {
+        value / 10_u64.saturating_pow((-shift) as u32)
+    }
+}
+
+/// Render a typed `PositionDirection` as the `"long"`/`"short"` string the
+/// crate's `math::standardize_price` wrapper expects, so order normalization
+/// routes through the same exported helper Python callers use.
+fn direction_str(direction: drift_rs::types::PositionDirection) -> &'static str {
+    match direction {
+        drift_rs::types::PositionDirection::Long => "long",
+        drift_rs::types::PositionDirection::Short => "short",
+    }
+}
+
+/// Default staleness threshold (in slots) tuned per oracle source.
+///
+/// Push-based feeds update frequently and should be considered stale quickly;
+/// on-demand/pull sources are cranked less often and get a looser bound. Used
+/// when the caller does not pass an explicit `max_slot_lag`.
+fn default_max_slot_lag(source: Option<drift_rs::types::OracleSource>) -> u64 {
+    match source {
+        Some(drift_rs::types::OracleSource::Pyth)
+        | Some(drift_rs::types::OracleSource::Switchboard) => 10,
+        _ => 25,
+    }
+}
+
+/// Build the oracle dict shared by `get_perp_oracle`/`get_spot_oracle`,
+/// enriched with staleness fields.
+///
+/// `slots_stale` is the number of slots the feed lags the live chain, measured
+/// as `current_slot - oracle.slot` rather than the `delay` frozen into the
+/// cache entry at write time — a fully frozen feed keeps a constant `delay` but
+/// falls ever further behind the current slot, which is exactly the case this
+/// guards against. `is_stale` flags a feed lagging past the threshold, and
+/// `max_slot_lag` echoes the threshold used so callers can see how the verdict
+/// was reached.
+fn build_oracle_dict(
+    py: Python<'_>,
+    oracle: &drift_rs::types::OraclePriceDataAndSlot,
+    source: Option<drift_rs::types::OracleSource>,
+    current_slot: u64,
+    max_slot_lag: Option<u64>,
+) -> PyResult<Py<PyAny>> {
+    let threshold = max_slot_lag.unwrap_or_else(|| default_max_slot_lag(source));
+    let slots_stale = current_slot.saturating_sub(oracle.slot);
+    let is_stale = slots_stale > threshold;
+
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("price", oracle.data.price)?;
+    dict.set_item("confidence", oracle.data.confidence)?;
+    dict.set_item("delay", oracle.data.delay)?;
+    dict.set_item("slot", oracle.slot)?;
+    dict.set_item("slots_stale", slots_stale)?;
+    dict.set_item("is_stale", is_stale)?;
+    dict.set_item("max_slot_lag", threshold)?;
+    Ok(dict.into_any().unbind())
 }
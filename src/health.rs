@@ -0,0 +1,162 @@
+//! Collateral and margin-health computation for user accounts.
+//!
+//! This mirrors how the on-chain program sizes margin: each spot balance is
+//! valued at its oracle price and scaled by the market's asset/liability
+//! weight, each perp position is valued at the perp oracle price (including
+//! unrealized PnL) and charged the market's margin ratio. The initial and
+//! maintenance variants are computed in a single pass so liquidator and risk
+//! bots get both without re-walking the account.
+
+use drift_rs::types::accounts::User;
+use drift_rs::types::{MarketId, SpotBalanceType};
+use pyo3::prelude::*;
+
+/// Weight precision used by spot asset/liability weights (1e4).
+const SPOT_WEIGHT_PRECISION: i128 = 10_000;
+/// Margin-ratio precision used by perp markets (1e4).
+const MARGIN_PRECISION: i128 = 10_000;
+/// Precision of the market's cumulative deposit/borrow interest index (1e10).
+const SPOT_CUMULATIVE_INTEREST_PRECISION: i128 = 10_000_000_000;
+/// Precision of a spot position's `scaled_balance` (1e9).
+const SPOT_BALANCE_PRECISION: i128 = 1_000_000_000;
+/// Base-asset precision for perp positions (1e9).
+const BASE_PRECISION: i128 = 1_000_000_000;
+/// AMM reserve precision, used when unwinding cumulative funding (1e9).
+const AMM_RESERVE_PRECISION: i128 = 1_000_000_000;
+/// Extra buffer precision on cumulative funding rates (1e3).
+const FUNDING_RATE_BUFFER_PRECISION: i128 = 1_000;
+
+/// One variant (initial or maintenance) of the margin computation.
+#[derive(Default)]
+struct Accumulator {
+    /// Weighted collateral in quote precision (1e6).
+    total_collateral: i128,
+    /// Margin requirement in quote precision (1e6).
+    margin_requirement: i128,
+}
+
+/// Compute initial and maintenance health for `user` using cached market and
+/// oracle data, returning the summary dict shared by the async `get_health`
+/// and the synchronous `try_get_health`.
+pub(crate) fn compute_health(
+    py: Python<'_>,
+    client: &drift_rs::DriftClient,
+    user: &User,
+) -> PyResult<Py<PyAny>> {
+    let mut initial = Accumulator::default();
+    let mut maintenance = Accumulator::default();
+
+    // Spot balances: value at oracle price, weight deposits as collateral and
+    // borrows as liabilities.
+    for position in user.spot_positions.iter() {
+        if position.scaled_balance == 0 {
+            continue;
+        }
+        let Some(oracle) =
+            client.try_get_oracle_price_data_and_slot(MarketId::spot(position.market_index))
+        else {
+            continue;
+        };
+        let Ok(market) = client.try_get_spot_market_account(position.market_index) else {
+            continue;
+        };
+
+        let price = oracle.data.price as i128;
+        // `scaled_balance` is in 1e9 precision; scale it by the market's
+        // cumulative deposit/borrow interest index to recover the real token
+        // amount in the market's native decimals, then value it at the oracle
+        // price (1e6), dividing out the token decimals to land in 1e6 quote.
+        let cumulative_interest = match position.balance_type {
+            SpotBalanceType::Deposit => market.cumulative_deposit_interest,
+            SpotBalanceType::Borrow => market.cumulative_borrow_interest,
+        } as i128;
+        let token_amount = position.scaled_balance as i128 * cumulative_interest
+            / (SPOT_CUMULATIVE_INTEREST_PRECISION * SPOT_BALANCE_PRECISION
+                / 10_i128.pow(market.decimals as u32));
+        let value = token_amount * price / 10_i128.pow(market.decimals as u32);
+
+        match position.balance_type {
+            SpotBalanceType::Deposit => {
+                initial.total_collateral +=
+                    value * market.initial_asset_weight as i128 / SPOT_WEIGHT_PRECISION;
+                maintenance.total_collateral +=
+                    value * market.maintenance_asset_weight as i128 / SPOT_WEIGHT_PRECISION;
+            }
+            SpotBalanceType::Borrow => {
+                initial.margin_requirement +=
+                    value * market.initial_liability_weight as i128 / SPOT_WEIGHT_PRECISION;
+                maintenance.margin_requirement +=
+                    value * market.maintenance_liability_weight as i128 / SPOT_WEIGHT_PRECISION;
+            }
+        }
+    }
+
+    // Perp positions: value the base at the perp oracle price, fold in
+    // unrealized PnL/funding, and charge the market's margin ratio.
+    for position in user.perp_positions.iter() {
+        if position.base_asset_amount == 0 && position.quote_asset_amount == 0 {
+            continue;
+        }
+        let Some(oracle) =
+            client.try_get_oracle_price_data_and_slot(MarketId::perp(position.market_index))
+        else {
+            continue;
+        };
+        let Ok(market) = client.try_get_perp_market_account(position.market_index) else {
+            continue;
+        };
+
+        let price = oracle.data.price as i128;
+        let base_value = position.base_asset_amount as i128 * price / BASE_PRECISION;
+
+        // Unsettled funding is the delta between the market's cumulative
+        // funding rate (long or short side, by position sign) and the rate
+        // last applied to this position, carried on the base size. Mirrors the
+        // on-chain `calculate_funding_payment` sign convention.
+        let amm_cumulative_funding = if position.base_asset_amount >= 0 {
+            market.amm.cumulative_funding_rate_long
+        } else {
+            market.amm.cumulative_funding_rate_short
+        };
+        let funding_delta =
+            amm_cumulative_funding - position.last_cumulative_funding_rate as i128;
+        let unsettled_funding = -(funding_delta * position.base_asset_amount as i128
+            / AMM_RESERVE_PRECISION
+            / FUNDING_RATE_BUFFER_PRECISION);
+
+        let unrealized = position.quote_asset_amount as i128 + base_value + unsettled_funding;
+        initial.total_collateral += unrealized;
+        maintenance.total_collateral += unrealized;
+
+        let exposure = base_value.abs();
+        initial.margin_requirement +=
+            exposure * market.margin_ratio_initial as i128 / MARGIN_PRECISION;
+        maintenance.margin_requirement +=
+            exposure * market.margin_ratio_maintenance as i128 / MARGIN_PRECISION;
+    }
+
+    // Free collateral and the initial requirement gate new risk, so they use
+    // initial-weighted collateral. The liquidation verdict and margin ratio are
+    // maintenance concepts and must compare the maintenance requirement against
+    // maintenance-weighted collateral.
+    let free_collateral = initial.total_collateral - initial.margin_requirement;
+    let margin_ratio = if maintenance.total_collateral > 0 {
+        maintenance.margin_requirement as f64 / maintenance.total_collateral as f64
+    } else {
+        0.0
+    };
+    let can_be_liquidated = maintenance.margin_requirement > maintenance.total_collateral;
+
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("total_collateral", initial.total_collateral)?;
+    dict.set_item("maintenance_total_collateral", maintenance.total_collateral)?;
+    dict.set_item("initial_margin_requirement", initial.margin_requirement)?;
+    dict.set_item(
+        "maintenance_margin_requirement",
+        maintenance.margin_requirement,
+    )?;
+    dict.set_item("free_collateral", free_collateral)?;
+    dict.set_item("margin_ratio", margin_ratio)?;
+    dict.set_item("can_be_liquidated", can_be_liquidated)?;
+    Ok(dict.into_any().unbind())
+}
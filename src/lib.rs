@@ -5,9 +5,28 @@ pub mod async_test;
 pub mod cache_demo;
 pub mod constants;
 pub mod drift_client;
+pub mod health;
 pub mod math;
+pub mod observability;
+pub mod pg_recorder;
 pub mod pyth_lazer;
+pub mod streams;
 pub mod utils;
+pub mod worker_manager;
+
+/// Boxed layer over the root `Registry`, used for the reloadable OTLP slot.
+#[cfg(all(feature = "observability", not(feature = "tokio-console")))]
+pub(crate) type BoxedLayer =
+    Box<dyn tracing_subscriber::Layer<tracing_subscriber::Registry> + Send + Sync>;
+
+/// Reload handle for the OpenTelemetry layer. The global subscriber is built
+/// once at import time with an empty reloadable slot; `init_observability`
+/// swaps the real OTLP layer in via this handle so it is actually registered
+/// alongside the fmt layer rather than racing a second `try_init`.
+#[cfg(all(feature = "observability", not(feature = "tokio-console")))]
+pub(crate) static OTEL_RELOAD: std::sync::OnceLock<
+    tracing_subscriber::reload::Handle<Option<BoxedLayer>, tracing_subscriber::Registry>,
+> = std::sync::OnceLock::new();
 
 fn init_observability() {
     static INIT: std::sync::Once = std::sync::Once::new();
@@ -21,14 +40,28 @@ fn init_observability() {
 
         #[cfg(all(feature = "observability", not(feature = "tokio-console")))]
         {
+            use tracing_subscriber::prelude::*;
+
             let filter = tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
 
-            let _ = tracing_subscriber::fmt()
-                .with_env_filter(filter)
-                .with_thread_ids(true)
-                .with_thread_names(true)
-                .try_init();
+            // Empty reloadable OTLP slot; `init_observability` fills it later.
+            let (otel_layer, handle) =
+                tracing_subscriber::reload::Layer::new(None::<BoxedLayer>);
+
+            let layers: Vec<BoxedLayer> = vec![
+                Box::new(filter),
+                Box::new(
+                    tracing_subscriber::fmt::layer()
+                        .with_thread_ids(true)
+                        .with_thread_names(true),
+                ),
+                Box::new(otel_layer),
+            ];
+
+            if tracing_subscriber::registry().with(layers).try_init().is_ok() {
+                let _ = OTEL_RELOAD.set(handle);
+            }
         }
     });
 }
@@ -76,8 +109,14 @@ fn _driftpyrs(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     m.add_function(wrap_pyfunction!(async_test::sleep_and_return, m)?)?;
 
+    m.add_function(wrap_pyfunction!(observability::init_observability, m)?)?;
+    m.add_function(wrap_pyfunction!(observability::shutdown_observability, m)?)?;
+
     m.add_class::<cache_demo::CacheDemo>()?;
+    m.add_class::<worker_manager::WorkerManager>()?;
+    m.add_class::<pg_recorder::PgRecorder>()?;
     m.add_class::<drift_client::DriftClient>()?;
+    m.add_class::<streams::SubscriptionHandle>()?;
 
     let pyth_lazer = PyModule::new(m.py(), "pyth_lazer")?;
     pyth_lazer.add_function(wrap_pyfunction!(
@@ -88,6 +127,11 @@ fn _driftpyrs(m: &Bound<'_, PyModule>) -> PyResult<()> {
         pyth_lazer::perp_market_index_to_feed_id,
         &pyth_lazer
     )?)?;
+    pyth_lazer.add_function(wrap_pyfunction!(pyth_lazer::parse_lazer_update, &pyth_lazer)?)?;
+    pyth_lazer.add_function(wrap_pyfunction!(
+        pyth_lazer::build_lazer_update_ixs,
+        &pyth_lazer
+    )?)?;
     m.add_submodule(&pyth_lazer)?;
 
     Ok(())
@@ -0,0 +1,127 @@
+use pyo3::prelude::*;
+
+/// Default OTLP collector endpoint (gRPC) used when neither the argument nor
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` is set.
+pub const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4317";
+
+/// Default `service.name` attached to exported spans.
+pub const DEFAULT_SERVICE_NAME: &str = "driftpyrs";
+
+/// Resolve the OTLP endpoint the same way `build_info` resolves other
+/// env-driven settings: explicit argument wins, then the standard
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` env var, then the built-in default.
+pub fn resolve_endpoint(endpoint: Option<String>) -> String {
+    endpoint
+        .or_else(|| std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok())
+        .unwrap_or_else(|| DEFAULT_OTLP_ENDPOINT.to_string())
+}
+
+#[cfg(feature = "observability")]
+static PROVIDER: std::sync::Mutex<Option<opentelemetry_sdk::trace::TracerProvider>> =
+    std::sync::Mutex::new(None);
+
+/// Install a `tracing_subscriber` registry that exports spans to an
+/// OpenTelemetry OTLP collector alongside the usual fmt layer.
+///
+/// Spans are batched and shipped over gRPC to `endpoint` (defaulting to
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` or `http://localhost:4317`), tagged with a
+/// `service.name` resource (defaulting to `driftpyrs`). Call
+/// `shutdown_observability()` on teardown to flush pending spans.
+#[pyfunction]
+#[pyo3(signature = (endpoint=None, service_name=None))]
+pub fn init_observability(endpoint: Option<String>, service_name: Option<String>) -> PyResult<()> {
+    let endpoint = resolve_endpoint(endpoint);
+    let service_name = service_name.unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_string());
+
+    #[cfg(feature = "observability")]
+    {
+        use opentelemetry::trace::TracerProvider as _;
+        use opentelemetry::KeyValue;
+        use opentelemetry_otlp::WithExportConfig;
+        use opentelemetry_sdk::{trace, Resource};
+
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint.clone())
+            .build()
+            .map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to build OTLP exporter: {}",
+                    e
+                ))
+            })?;
+
+        let provider = trace::TracerProvider::builder()
+            .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+            .with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                service_name.clone(),
+            )]))
+            .build();
+
+        let tracer = provider.tracer(service_name.clone());
+        opentelemetry::global::set_tracer_provider(provider.clone());
+
+        // The global subscriber was already installed at import time with a
+        // reloadable OTLP slot; swap the real layer into it rather than calling
+        // `try_init` a second time (which would silently fail to take effect).
+        #[cfg(not(feature = "tokio-console"))]
+        {
+            use tracing_subscriber::Layer as _;
+            let layer: crate::BoxedLayer =
+                tracing_opentelemetry::layer().with_tracer(tracer).boxed();
+            match crate::OTEL_RELOAD.get() {
+                Some(handle) => handle.reload(Some(layer)).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to install OTLP layer: {}",
+                        e
+                    ))
+                })?,
+                None => {
+                    return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                        "tracing registry was not initialized; cannot install OTLP export",
+                    ));
+                }
+            }
+        }
+
+        // Under the tokio-console subscriber there is no reloadable registry to
+        // attach to, so OTLP export cannot be installed.
+        #[cfg(feature = "tokio-console")]
+        {
+            let _ = tracer;
+            return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                "OTLP export is incompatible with the tokio-console subscriber",
+            ));
+        }
+
+        #[allow(unreachable_code)]
+        {
+            *PROVIDER.lock().unwrap() = Some(provider);
+            tracing::info!(endpoint, service_name, "init_observability - OTLP export installed");
+        }
+    }
+
+    #[cfg(not(feature = "observability"))]
+    {
+        let _ = (endpoint, service_name);
+    }
+
+    Ok(())
+}
+
+/// Flush and shut down the OTLP batch span processor so buffered traces are not
+/// lost on interpreter teardown. Safe to call even if never initialized.
+#[pyfunction]
+pub fn shutdown_observability() -> PyResult<()> {
+    #[cfg(feature = "observability")]
+    {
+        if let Some(provider) = PROVIDER.lock().unwrap().take() {
+            if let Err(e) = provider.shutdown() {
+                tracing::warn!(error = %e, "shutdown_observability - failed to flush span processor");
+            }
+        }
+    }
+
+    Ok(())
+}
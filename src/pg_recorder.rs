@@ -0,0 +1,199 @@
+use bb8::Pool;
+use bb8_postgres::PostgresConnectionManager;
+use dashmap::DashMap;
+use pyo3::prelude::*;
+use std::sync::Arc;
+use tokio_postgres::NoTls;
+
+type PgPool = Pool<PostgresConnectionManager<NoTls>>;
+
+/// Embedded schema applied on first connect so snapshots survive restarts
+/// without an external migration step.
+const MIGRATION: &str = "CREATE TABLE IF NOT EXISTS cache_snapshots (\
+    key TEXT NOT NULL, \
+    value TEXT NOT NULL, \
+    recorded_at TIMESTAMPTZ NOT NULL\
+)";
+
+/// Durable Postgres sink for cached feed/price data.
+///
+/// `PgRecorder` mirrors the `CacheDemo`/`WorkerManager` pattern: a shared
+/// `DashMap` is written synchronously from the Python side via `record`, while
+/// a background Tokio task periodically drains it and batch-inserts rows into
+/// the `cache_snapshots` table so price/market snapshots survive process
+/// restarts and can be queried by downstream analytics. The `bb8` pool is
+/// shared (cloned) between the flush task and the synchronous query helpers.
+#[pyclass]
+pub struct PgRecorder {
+    pool: PgPool,
+    cache: Arc<DashMap<String, String>>,
+}
+
+#[pymethods]
+impl PgRecorder {
+    /// Build a recorder over a `bb8` pool for `conn_str`.
+    ///
+    /// The pool is created without probing connections up front; the schema
+    /// migration runs lazily on the flush task's first checkout.
+    #[new]
+    fn new(conn_str: &str) -> PyResult<Self> {
+        let manager = PostgresConnectionManager::new_from_stringlike(conn_str, NoTls)
+            .map_err(|e| {
+                pyo3::exceptions::PyValueError::new_err(format!("Invalid connection string: {}", e))
+            })?;
+        let pool = Pool::builder().build_unchecked(manager);
+
+        #[cfg(feature = "observability")]
+        tracing::info!("PgRecorder::new - pool created");
+
+        Ok(Self {
+            pool,
+            cache: Arc::new(DashMap::new()),
+        })
+    }
+
+    /// Stage a key/value for the next flush (synchronous cache write).
+    fn record(&self, key: String, value: String) {
+        self.cache.insert(key, value);
+    }
+
+    /// Start the background flush task, draining the cache into Postgres every
+    /// `interval_secs` seconds. Runs the schema migration on first connect.
+    #[pyo3(signature = (interval_secs=5))]
+    fn start<'py>(&self, py: Python<'py>, interval_secs: u64) -> PyResult<Bound<'py, PyAny>> {
+        let pool = self.pool.clone();
+        let cache = Arc::clone(&self.cache);
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            tokio::spawn(async move {
+                // Apply the embedded migration once before ticking.
+                if let Ok(client) = pool.get().await {
+                    let _ = client.batch_execute(MIGRATION).await;
+                }
+
+                loop {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(interval_secs)).await;
+
+                    // Snapshot the current entries, then clear what we drained.
+                    let batch: Vec<(String, String)> = cache
+                        .iter()
+                        .map(|e| (e.key().clone(), e.value().clone()))
+                        .collect();
+                    if batch.is_empty() {
+                        continue;
+                    }
+
+                    let client = match pool.get().await {
+                        Ok(client) => client,
+                        Err(_e) => {
+                            #[cfg(feature = "observability")]
+                            tracing::warn!(error = %_e, "PgRecorder flush - failed to get client");
+                            continue;
+                        }
+                    };
+
+                    // Batch-insert the drained rows. Each row binds 2 params, so
+                    // chunk to stay well under Postgres' 65535-parameter cap on
+                    // large flushes rather than emitting one giant statement.
+                    const ROWS_PER_STATEMENT: usize = 1000;
+                    for chunk in batch.chunks(ROWS_PER_STATEMENT) {
+                        let mut sql = String::from(
+                            "INSERT INTO cache_snapshots (key, value, recorded_at) VALUES ",
+                        );
+                        let mut params: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> =
+                            Vec::with_capacity(chunk.len() * 2);
+                        for (i, (key, value)) in chunk.iter().enumerate() {
+                            if i > 0 {
+                                sql.push_str(", ");
+                            }
+                            sql.push_str(&format!("(${}, ${}, NOW())", i * 2 + 1, i * 2 + 2));
+                            params.push(key);
+                            params.push(value);
+                        }
+
+                        match client.execute(sql.as_str(), &params).await {
+                            Ok(_n) => {
+                                // Compare-and-remove: only drop an entry whose value
+                                // is still the one we just persisted, so a `record`
+                                // landing between the snapshot and here survives to
+                                // the next flush instead of being silently dropped.
+                                for (key, value) in chunk {
+                                    cache.remove_if(key, |_, v| v == value);
+                                }
+                                #[cfg(feature = "observability")]
+                                tracing::debug!(
+                                    rows = _n,
+                                    "PgRecorder flush - inserted snapshot rows"
+                                );
+                            }
+                            Err(_e) => {
+                                #[cfg(feature = "observability")]
+                                tracing::warn!(error = %_e, "PgRecorder flush - insert failed");
+                            }
+                        }
+                    }
+                }
+            });
+
+            Ok(())
+        })
+    }
+
+    /// Fetch the most recent persisted values for `key`, newest first.
+    fn recent<'py>(
+        &self,
+        py: Python<'py>,
+        key: String,
+        limit: i64,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let pool = self.pool.clone();
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let client = pool.get().await.map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to get client: {}", e))
+            })?;
+
+            let rows = client
+                .query(
+                    "SELECT value, recorded_at FROM cache_snapshots \
+                     WHERE key = $1 ORDER BY recorded_at DESC LIMIT $2",
+                    &[&key, &limit],
+                )
+                .await
+                .map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Query failed: {}", e))
+                })?;
+
+            Python::attach(|py| {
+                let out = pyo3::types::PyList::empty(py);
+                for row in rows {
+                    let value: String = row.get(0);
+                    let recorded_at: std::time::SystemTime = row.get(1);
+                    let ts = recorded_at
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_secs_f64())
+                        .unwrap_or(0.0);
+                    let dict = pyo3::types::PyDict::new(py);
+                    dict.set_item("value", value)?;
+                    dict.set_item("recorded_at", ts)?;
+                    out.append(dict)?;
+                }
+                Ok(out.into_any().unbind())
+            })
+        })
+    }
+
+    /// Report pool health (idle/active connection counts) so operators can
+    /// size the pool for high-frequency feeds.
+    fn stats(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let state = self.pool.state();
+        let dict = pyo3::types::PyDict::new(py);
+        dict.set_item("connections", state.connections)?;
+        dict.set_item("idle_connections", state.idle_connections)?;
+        dict.set_item(
+            "active_connections",
+            state.connections.saturating_sub(state.idle_connections),
+        )?;
+        Ok(dict.into_any().unbind())
+    }
+}
@@ -1,4 +1,6 @@
 use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyList};
+use solana_sdk::instruction::Instruction;
 
 #[pyfunction]
 pub fn feed_id_to_perp_market_index(feed_id: u32) -> Option<u16> {
@@ -9,3 +11,66 @@ pub fn feed_id_to_perp_market_index(feed_id: u32) -> Option<u16> {
 pub fn perp_market_index_to_feed_id(market_index: u16) -> Option<u32> {
     drift_rs::constants::perp_market_index_to_pyth_lazer_feed_id(market_index)
 }
+
+/// Verify and parse a signed Lazer price update into an oracle dict.
+///
+/// `message` is the serialized Lazer price payload and `signatures` the
+/// verifier signatures guarding it. Returns a dict with `price`, `confidence`,
+/// `exponent`, and `publish_time`, or raises `ValueError` if verification or
+/// parsing fails. `price` and `confidence` are the feed's raw mantissas at
+/// `10^exponent`, not rescaled to the 1e6 precision `get_perp_oracle` uses.
+#[pyfunction]
+pub fn parse_lazer_update(
+    py: Python<'_>,
+    message: Vec<u8>,
+    signatures: Vec<Vec<u8>>,
+) -> PyResult<Py<PyAny>> {
+    let update = drift_rs::pyth_lazer::parse_update(&message, &signatures).map_err(|e| {
+        pyo3::exceptions::PyValueError::new_err(format!("Invalid Lazer update: {}", e))
+    })?;
+
+    let dict = PyDict::new(py);
+    dict.set_item("price", update.price)?;
+    dict.set_item("confidence", update.confidence)?;
+    dict.set_item("exponent", update.exponent)?;
+    dict.set_item("publish_time", update.publish_time)?;
+    Ok(dict.into_any().unbind())
+}
+
+/// Build the instruction(s) needed to post a Lazer update to its derived oracle
+/// account (see `addresses::derive_pyth_lazer_oracle`).
+///
+/// Each instruction is returned as a dict with `program_id`, `accounts`
+/// (`{pubkey, is_signer, is_writable}`), and raw `data` bytes, ready for the
+/// caller to assemble into a transaction.
+#[pyfunction]
+pub fn build_lazer_update_ixs(
+    py: Python<'_>,
+    feed_id: u32,
+    message: Vec<u8>,
+    signatures: Vec<Vec<u8>>,
+) -> PyResult<Vec<Py<PyAny>>> {
+    let ixs = drift_rs::pyth_lazer::post_update_ixs(feed_id, &message, &signatures).map_err(|e| {
+        pyo3::exceptions::PyValueError::new_err(format!("Failed to build Lazer update ix: {}", e))
+    })?;
+
+    ixs.iter().map(|ix| instruction_to_dict(py, ix)).collect()
+}
+
+/// Serialize a Solana `Instruction` into a plain dict for the Python side.
+fn instruction_to_dict(py: Python<'_>, ix: &Instruction) -> PyResult<Py<PyAny>> {
+    let accounts = PyList::empty(py);
+    for meta in &ix.accounts {
+        let account = PyDict::new(py);
+        account.set_item("pubkey", meta.pubkey.to_string())?;
+        account.set_item("is_signer", meta.is_signer)?;
+        account.set_item("is_writable", meta.is_writable)?;
+        accounts.append(account)?;
+    }
+
+    let dict = PyDict::new(py);
+    dict.set_item("program_id", ix.program_id.to_string())?;
+    dict.set_item("accounts", accounts)?;
+    dict.set_item("data", PyBytes::new(py, &ix.data))?;
+    Ok(dict.into_any().unbind())
+}
@@ -0,0 +1,122 @@
+//! Live fill and orderbook streams pushed into Python via callbacks.
+//!
+//! `DriftClient::subscribe_fills` and `DriftClient::subscribe_orderbook` each
+//! spawn a background task on the shared tokio runtime that normalizes Drift
+//! events into UI-unit dicts and invokes a registered Python callable. Both
+//! return a [`SubscriptionHandle`] so the stream can be cancelled.
+
+use pyo3::prelude::*;
+
+/// Base-asset precision (1e9) used to convert raw amounts to UI units.
+const BASE_PRECISION: f64 = 1_000_000_000.0;
+/// Quote/price precision (1e6) used to convert raw amounts to UI units.
+const PRICE_PRECISION: f64 = 1_000_000.0;
+
+/// Cancellable handle for a fills/orderbook subscription.
+///
+/// Dropping the handle does not stop the stream; call `cancel()` to abort the
+/// background task explicitly.
+#[pyclass]
+pub struct SubscriptionHandle {
+    abort: Option<tokio::task::AbortHandle>,
+}
+
+impl SubscriptionHandle {
+    pub(crate) fn new(abort: tokio::task::AbortHandle) -> Self {
+        Self { abort: Some(abort) }
+    }
+}
+
+#[pymethods]
+impl SubscriptionHandle {
+    /// Cancel the subscription, stopping its background task.
+    fn cancel(&mut self) {
+        if let Some(abort) = self.abort.take() {
+            abort.abort();
+        }
+    }
+
+    /// Whether the subscription task is still running.
+    fn is_active(&self) -> bool {
+        self.abort
+            .as_ref()
+            .map(|a| !a.is_finished())
+            .unwrap_or(false)
+    }
+}
+
+/// Invoke a Python callback with a single dict argument, swallowing errors so a
+/// misbehaving callback can't tear down the streaming task.
+pub(crate) fn dispatch(callback: &Py<PyAny>, build: impl FnOnce(Python<'_>) -> PyResult<Py<PyAny>>) {
+    Python::attach(|py| {
+        match build(py) {
+            Ok(payload) => {
+                if let Err(_e) = callback.call1(py, (payload,)) {
+                    #[cfg(feature = "observability")]
+                    tracing::warn!(error = %_e, "stream callback raised");
+                }
+            }
+            Err(_e) => {
+                #[cfg(feature = "observability")]
+                tracing::warn!(error = %_e, "failed to build stream payload");
+            }
+        }
+    });
+}
+
+/// Build the normalized fill dict delivered to `subscribe_fills` callbacks.
+///
+/// Amounts are converted from raw on-chain integers to human/UI units and the
+/// fill price is derived as quote/base.
+pub(crate) fn fill_to_dict(
+    py: Python<'_>,
+    market_index: u16,
+    maker: Option<String>,
+    taker: Option<String>,
+    base_filled: u64,
+    quote_filled: u64,
+    taker_side: &str,
+    slot: u64,
+    ts: i64,
+) -> PyResult<Py<PyAny>> {
+    let base = base_filled as f64 / BASE_PRECISION;
+    let quote = quote_filled as f64 / PRICE_PRECISION;
+    let price = if base > 0.0 { quote / base } else { 0.0 };
+
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("market_index", market_index)?;
+    dict.set_item("maker", maker)?;
+    dict.set_item("taker", taker)?;
+    dict.set_item("base_amount", base)?;
+    dict.set_item("quote_amount", quote)?;
+    dict.set_item("price", price)?;
+    dict.set_item("taker_side", taker_side)?;
+    dict.set_item("slot", slot)?;
+    dict.set_item("ts", ts)?;
+    Ok(dict.into_any().unbind())
+}
+
+/// Build the orderbook dict delivered to `subscribe_orderbook` callbacks, with
+/// bid/ask levels as `{price, size}` dicts in UI units.
+pub(crate) fn l2_to_dict(
+    py: Python<'_>,
+    market_index: u16,
+    book: &drift_rs::dlob::L2Orderbook,
+) -> PyResult<Py<PyAny>> {
+    let level_list = |levels: &[drift_rs::dlob::L2Level]| -> PyResult<Py<PyAny>> {
+        let out = pyo3::types::PyList::empty(py);
+        for level in levels {
+            let d = pyo3::types::PyDict::new(py);
+            d.set_item("price", level.price as f64 / PRICE_PRECISION)?;
+            d.set_item("size", level.size as f64 / BASE_PRECISION)?;
+            out.append(d)?;
+        }
+        Ok(out.into_any().unbind())
+    };
+
+    let dict = pyo3::types::PyDict::new(py);
+    dict.set_item("market_index", market_index)?;
+    dict.set_item("bids", level_list(&book.bids)?)?;
+    dict.set_item("asks", level_list(&book.asks)?)?;
+    Ok(dict.into_any().unbind())
+}
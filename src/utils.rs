@@ -30,6 +30,7 @@ pub fn build_info(py: Python<'_>) -> PyResult<Py<PyAny>> {
     let tokio_unstable = cfg!(tokio_unstable);
     let default_addr = "127.0.0.1:6669".to_string();
     let env_bind = std::env::var("TOKIO_CONSOLE_BIND").ok();
+    let otlp_endpoint = crate::observability::resolve_endpoint(None);
 
     let d = PyDict::new(py);
     d.set_item("observability", observability)?;
@@ -37,5 +38,6 @@ pub fn build_info(py: Python<'_>) -> PyResult<Py<PyAny>> {
     d.set_item("tokio_unstable", tokio_unstable)?;
     d.set_item("tokio_console_default_addr", default_addr)?;
     d.set_item("tokio_console_bind_env", env_bind)?;
+    d.set_item("otlp_endpoint", otlp_endpoint)?;
     Ok(d.into())
 }
@@ -0,0 +1,298 @@
+use dashmap::DashMap;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::watch;
+
+/// Current lifecycle state of a registered background worker.
+///
+/// A worker is `Active` while it is ticking normally, `Idle` once a `pause`
+/// request has taken effect, and `Dead` if its loop body returned an error or
+/// it was cancelled (the string carries the reason for operators).
+#[derive(Clone, Debug)]
+pub enum WorkerStatus {
+    Active,
+    Idle,
+    Dead(String),
+}
+
+impl WorkerStatus {
+    /// Short, stable label used when reporting status to Python.
+    fn label(&self) -> String {
+        match self {
+            WorkerStatus::Active => "active".to_string(),
+            WorkerStatus::Idle => "idle".to_string(),
+            WorkerStatus::Dead(reason) => format!("dead: {}", reason),
+        }
+    }
+}
+
+/// Control signal sent to a worker over its `watch` channel. The worker loop
+/// reads the latest value at the top of each iteration.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Control {
+    Run,
+    Pause,
+    Cancel,
+}
+
+/// Per-worker bookkeeping held by the manager.
+///
+/// The worker task holds clones of `status` / `last_tick_ts` so it can report
+/// progress; the manager keeps the `control` sender so Python can steer it.
+struct WorkerHandle {
+    control: watch::Sender<Control>,
+    status: Arc<Mutex<WorkerStatus>>,
+    last_tick_ts: Arc<AtomicI64>,
+}
+
+/// Current unix timestamp in milliseconds (0 if the clock is before the epoch).
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+/// Manages named Tokio background workers with pause/resume/cancel control.
+///
+/// This is the lifecycle-aware successor to `CacheDemo`'s fire-and-forget
+/// `start_updates`: every spawned task is registered under a name with a
+/// `WorkerStatus`, so operators can see which market-data updaters are alive,
+/// stalled, or crashed and can stop individual feeds without tearing the whole
+/// process down. The shared `cache` mirrors the `CacheDemo` pattern
+/// (synchronous Python reads, async Tokio writes) and is exposed so durable
+/// sinks and snapshot writers can attach as additional workers.
+#[pyclass]
+pub struct WorkerManager {
+    workers: Arc<DashMap<String, WorkerHandle>>,
+    cache: Arc<DashMap<String, String>>,
+}
+
+impl WorkerManager {
+    /// Register and spawn a worker driving `body`, transitioning its status as
+    /// it runs. `body` is invoked once per tick; returning `Err` moves the
+    /// worker to `Dead` and stops the loop.
+    ///
+    /// Shared helper so the snapshot writer and other internal feeds register
+    /// exactly like the demo counter updater.
+    pub(crate) fn spawn_worker<F, Fut>(&self, name: String, interval_ms: u64, mut body: F)
+    where
+        F: FnMut(u64) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Result<(), String>> + Send,
+    {
+        let (control, mut control_rx) = watch::channel(Control::Run);
+        let status = Arc::new(Mutex::new(WorkerStatus::Active));
+        let last_tick_ts = Arc::new(AtomicI64::new(0));
+
+        let task_status = Arc::clone(&status);
+        let task_last_tick = Arc::clone(&last_tick_ts);
+
+        tokio::spawn(async move {
+            let mut counter = 0u64;
+            loop {
+                let control = *control_rx.borrow();
+                match control {
+                    Control::Cancel => {
+                        *task_status.lock().unwrap() = WorkerStatus::Dead("cancelled".to_string());
+                        break;
+                    }
+                    Control::Pause => {
+                        *task_status.lock().unwrap() = WorkerStatus::Idle;
+                        // Block until a resume/cancel arrives rather than busy-looping.
+                        if control_rx.changed().await.is_err() {
+                            break;
+                        }
+                        continue;
+                    }
+                    Control::Run => {}
+                }
+
+                match body(counter).await {
+                    Ok(()) => {
+                        *task_status.lock().unwrap() = WorkerStatus::Active;
+                        task_last_tick.store(now_ms(), Ordering::Relaxed);
+                    }
+                    Err(reason) => {
+                        *task_status.lock().unwrap() = WorkerStatus::Dead(reason);
+                        break;
+                    }
+                }
+
+                counter = counter.wrapping_add(1);
+                tokio::time::sleep(tokio::time::Duration::from_millis(interval_ms)).await;
+            }
+        });
+
+        self.workers.insert(
+            name,
+            WorkerHandle {
+                control,
+                status,
+                last_tick_ts,
+            },
+        );
+    }
+
+    /// Send a control signal to a named worker, erroring if it is unknown.
+    fn signal(&self, name: &str, control: Control) -> PyResult<()> {
+        match self.workers.get(name) {
+            Some(handle) => {
+                let _ = handle.control.send(control);
+                Ok(())
+            }
+            None => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "no worker named '{}'",
+                name
+            ))),
+        }
+    }
+}
+
+#[pymethods]
+impl WorkerManager {
+    #[new]
+    fn new() -> Self {
+        #[cfg(feature = "observability")]
+        tracing::info!("WorkerManager::new - creating new instance");
+
+        Self {
+            workers: Arc::new(DashMap::new()),
+            cache: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Get a value from the shared cache synchronously (no await).
+    fn get(&self, key: &str) -> Option<String> {
+        self.cache.get(key).map(|v| v.clone())
+    }
+
+    /// Spawn a named background worker that ticks the shared cache.
+    ///
+    /// Unlike `CacheDemo::start_updates`, the task is registered under `name`
+    /// so it can later be paused, resumed, cancelled, and inspected via
+    /// `list_workers`. Returns once the worker is registered and running.
+    fn start_updates<'py>(&self, py: Python<'py>, name: String) -> PyResult<Bound<'py, PyAny>> {
+        let cache = Arc::clone(&self.cache);
+        let workers = Arc::clone(&self.workers);
+        let worker_cache = Arc::clone(&self.cache);
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            #[cfg(feature = "observability")]
+            tracing::info!(name, "WorkerManager::start_updates - spawning worker");
+
+            let manager = WorkerManager {
+                workers,
+                cache: worker_cache,
+            };
+            let key = name.clone();
+            manager.spawn_worker(name, 100, move |counter| {
+                let cache = Arc::clone(&cache);
+                let key = key.clone();
+                async move {
+                    cache.insert(key, counter.to_string());
+                    Ok(())
+                }
+            });
+
+            Ok(())
+        })
+    }
+
+    /// Spawn an optional worker that snapshots the shared cache to `path` on an
+    /// interval, so durable local state is written periodically rather than
+    /// only on demand.
+    ///
+    /// The advisory file lock is acquired once, up front, and held for the
+    /// worker's entire lifetime — not reacquired per tick — so a second process
+    /// cannot attach to the same snapshot path in the gaps between writes. A
+    /// lock conflict fails fast here with a `ValueError` rather than after the
+    /// worker is registered.
+    #[pyo3(signature = (name, path, interval_ms=1000))]
+    fn start_snapshot_worker<'py>(
+        &self,
+        py: Python<'py>,
+        name: String,
+        path: String,
+        interval_ms: u64,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let cache = Arc::clone(&self.cache);
+        let workers = Arc::clone(&self.workers);
+        let worker_cache = Arc::clone(&self.cache);
+
+        // Acquire and hold the lock for the worker's lifetime; the guarded
+        // handle moves into the tick closure so it lives as long as the task.
+        let file = crate::cache_demo::lock_snapshot(&path)
+            .map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let file = Arc::new(Mutex::new(file));
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            #[cfg(feature = "observability")]
+            tracing::info!(name, path, "WorkerManager::start_snapshot_worker - spawning worker");
+
+            let manager = WorkerManager {
+                workers,
+                cache: worker_cache,
+            };
+            manager.spawn_worker(name, interval_ms, move |_counter| {
+                let cache = Arc::clone(&cache);
+                let file = Arc::clone(&file);
+                async move {
+                    let mut file = file.lock().unwrap();
+                    crate::cache_demo::write_snapshot(&mut file, &cache)
+                }
+            });
+
+            Ok(())
+        })
+    }
+
+    /// Pause a worker; its status becomes `Idle` until resumed.
+    fn pause(&self, name: &str) -> PyResult<()> {
+        self.signal(name, Control::Pause)
+    }
+
+    /// Resume a paused worker; its status returns to `Active` on the next tick.
+    fn resume(&self, name: &str) -> PyResult<()> {
+        self.signal(name, Control::Run)
+    }
+
+    /// Cancel a worker; its loop stops and its status becomes `Dead`.
+    fn cancel(&self, name: &str) -> PyResult<()> {
+        self.signal(name, Control::Cancel)
+    }
+
+    /// List every registered worker as a `(name, status, last_tick_ts)` dict.
+    ///
+    /// `last_tick_ts` is unix milliseconds of the most recent successful tick,
+    /// or `None` if the worker has not ticked yet.
+    fn list_workers(&self, py: Python<'_>) -> PyResult<Vec<Py<PyAny>>> {
+        let mut out = Vec::with_capacity(self.workers.len());
+        for entry in self.workers.iter() {
+            let dict = PyDict::new(py);
+            dict.set_item("name", entry.key())?;
+            dict.set_item("status", entry.value().status.lock().unwrap().label())?;
+            let ts = entry.value().last_tick_ts.load(Ordering::Relaxed);
+            dict.set_item("last_tick_ts", if ts == 0 { None } else { Some(ts) })?;
+            out.push(dict.into_any().unbind());
+        }
+        Ok(out)
+    }
+
+    /// Get all keys in the shared cache.
+    fn keys(&self) -> Vec<String> {
+        self.cache.iter().map(|entry| entry.key().clone()).collect()
+    }
+
+    /// Get the number of entries in the shared cache.
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Check if the shared cache is empty.
+    fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}